@@ -1,7 +1,6 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #[cfg(feature = "std")]
-use std::io::{Cursor, ErrorKind, Read};
-#[cfg_attr(not(feature = "std"), allow(unused))]
+use std::io::{ErrorKind, Read};
 use core::hash::Hasher;
 
 /// Calculate the 32-bit hash of the provided slice `slice` using the initial seed `seed`.
@@ -12,6 +11,45 @@ pub fn hash(slice: &[u8], seed: u64) -> u32 {
     marvin32_hash(slice, seed)
 }
 
+/// Compile-time-evaluable equivalent of [`hash()`], usable in `const` contexts (e.g. to
+/// precompute the hash of a string literal for a `match` arm or a static perfect-hash table).
+///
+/// `chunks_exact`, iterator `fold`, and slice `try_into` aren't usable in a `const fn`, so this
+/// is a hand-rolled version of the same loop rather than a call into [`hash()`].
+pub const fn hash_const(slice: &[u8], seed: u64) -> u32 {
+    let mut lo = seed as u32;
+    let mut hi = (seed >> 32) as u32;
+
+    let mut i = 0;
+    while i + 4 <= slice.len() {
+        let value = u32::from_le_bytes([slice[i], slice[i + 1], slice[i + 2], slice[i + 3]]);
+        marvin32_mix_const(&mut lo, &mut hi, value);
+        i += 4;
+    }
+
+    let mut final_value: u32 = 0x80;
+    let mut j = slice.len();
+    while j > i {
+        j -= 1;
+        final_value = (final_value << 8) | slice[j] as u32;
+    }
+
+    marvin32_mix_const(&mut lo, &mut hi, final_value);
+    marvin32_mix_const(&mut lo, &mut hi, 0);
+
+    lo ^ hi
+}
+
+#[inline(always)]
+const fn marvin32_mix_const(lo: &mut u32, hi: &mut u32, v: u32) {
+    *lo = lo.wrapping_add(v);
+    *hi ^= *lo;
+    *lo = lo.rotate_left(20).wrapping_add(*hi);
+    *hi = hi.rotate_left(9) ^ *lo;
+    *lo = lo.rotate_left(27).wrapping_add(*hi);
+    *hi = hi.rotate_left(19);
+}
+
 #[cfg(feature = "std")]
 /// Calculate the 32-bit hash of the provided `source` using the initial seed `seed`.
 ///
@@ -22,13 +60,31 @@ pub fn hash_streaming<R: Read>(source: &mut R, seed: u64) -> std::io::Result<u32
 }
 
 #[cfg(feature = "std")]
-/// An `[std::hash::Hasher]` implementation using the marvin32 hash algorithm.
+mod simd;
+#[cfg(feature = "std")]
+pub use simd::hash_many;
+
+/// A [`core::hash::Hasher`] implementation using the marvin32 hash algorithm.
+///
+/// Unlike [`hash_streaming()`], this doesn't need `Read` and works in `no_std` builds, making it
+/// the incremental API of choice for embedded/`no_std` contexts.
 pub struct Marvin32 {
+    // Only read back by the `digest` Reset impl and the `serde`/`zeroize` impls; with none of
+    // those enabled it's write-only, which is fine.
+    #[cfg_attr(
+        not(any(feature = "digest", feature = "zeroize", feature = "serde")),
+        allow(dead_code)
+    )]
+    seed: u64,
     state: Marvin32State,
-    buffer: Cursor<[u8; 4]>,
+    /// The 0-3 bytes that didn't fill a full 4-byte mixing word yet.
+    buf: [u8; 4],
+    /// How many of `buf`'s leading bytes are filled in.
+    len: u8,
 }
 
-#[cfg_attr(feature = "std", derive(Clone))]
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 struct Marvin32State {
     lo: u32,
     hi: u32,
@@ -52,7 +108,7 @@ fn marvin32_hash(ptr: &[u8], seed: u64) -> u32 {
     };
 
     let mut chunks = ptr.chunks_exact(4);
-    while let Some(chunk) = chunks.next() {
+    for chunk in chunks.by_ref() {
         let value = u32::from_le_bytes(chunk.try_into().unwrap());
         marvin32_mix(&mut state, value);
     }
@@ -123,61 +179,113 @@ fn read_chunked<R: Read, const C: usize>(src: &mut R, dst: &mut [u8; C]) -> std:
     }
 }
 
-#[cfg(feature = "std")]
 impl Marvin32 {
     pub fn new(seed: u64) -> Marvin32 {
         Self {
+            seed,
             state: Marvin32State {
                 lo: seed as u32,
                 hi: (seed >> 32) as u32,
             },
-            buffer: Cursor::new([0u8; 4]),
+            buf: [0u8; 4],
+            len: 0,
         }
     }
 }
 
 #[cfg(feature = "std")]
+/// A [`std::hash::BuildHasher`] that builds [`Marvin32`] hashers from a fixed, explicitly
+/// provided seed.
+///
+/// Prefer [`RandomMarvin32`] unless you need a specific, reproducible seed (e.g. for testing or
+/// for matching a hash computed elsewhere with the same seed).
+#[derive(Clone, Copy, Debug)]
+pub struct Marvin32BuildHasher {
+    seed: u64,
+}
+
+#[cfg(feature = "std")]
+impl Marvin32BuildHasher {
+    pub fn new(seed: u64) -> Self {
+        Self { seed }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::hash::BuildHasher for Marvin32BuildHasher {
+    type Hasher = Marvin32;
+
+    fn build_hasher(&self) -> Marvin32 {
+        Marvin32::new(self.seed)
+    }
+}
+
+#[cfg(feature = "std")]
+/// A [`std::hash::BuildHasher`] analogous to [`std::collections::hash_map::RandomState`]: it
+/// draws a random 64-bit seed once per process (lazily, from the OS RNG via `fastrand`) and
+/// reuses that seed for every [`Marvin32`] it builds, so a `HashMap<K, V, RandomMarvin32>` gets
+/// Marvin32's HashDoS resistance without callers having to manage a seed themselves.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RandomMarvin32;
+
+#[cfg(feature = "std")]
+impl RandomMarvin32 {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[cfg(feature = "std")]
+static PROCESS_SEED: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+
+#[cfg(feature = "std")]
+impl std::hash::BuildHasher for RandomMarvin32 {
+    type Hasher = Marvin32;
+
+    fn build_hasher(&self) -> Marvin32 {
+        let seed = *PROCESS_SEED.get_or_init(|| fastrand::u64(..));
+        Marvin32::new(seed)
+    }
+}
+
 impl Hasher for Marvin32 {
     fn write(&mut self, mut slice: &[u8]) {
-        use std::io::Write;
-
         // Assert we never start with a full buffer
-        debug_assert!(self.buffer.position() != 4);
+        debug_assert!(self.len != 4);
         // We need to consume our buffer first (by reaching 4 bytes)
-        let bytes_to_steal = 4 - self.buffer.position() as usize;
+        let bytes_to_steal = 4 - self.len as usize;
         if bytes_to_steal < 4 {
-            // Safe to unwrap since writes to an array-backed Cursor never fail
-            // Using write() instead of write_all() because it's faster and there's
-            // no need to use write_all() with an array-backed Cursor.
-            #[cfg(debug_assertions)]
-            let bytes_written = self.buffer.write(&slice[..bytes_to_steal]).unwrap();
-            #[cfg(not(debug_assertions))]
-            let bytes_written = unsafe { self.buffer.write(&slice[..bytes_to_steal]).unwrap_unchecked() };
-            debug_assert_eq!(bytes_written, slice.len().min(bytes_to_steal));
-            if bytes_written == bytes_to_steal {
+            let bytes_stolen = slice.len().min(bytes_to_steal);
+            self.buf[self.len as usize..self.len as usize + bytes_stolen]
+                .copy_from_slice(&slice[..bytes_stolen]);
+            self.len += bytes_stolen as u8;
+            if bytes_stolen == bytes_to_steal {
                 // We have a full buffer now
-                let value = u32::from_le_bytes(self.buffer.get_ref().as_slice().try_into().unwrap());
-                self.buffer.set_position(0);
+                let value = u32::from_le_bytes(self.buf);
+                self.len = 0;
                 marvin32_mix(&mut self.state, value);
+            } else {
+                // `slice` ran out before we could complete a 4-byte word; there's nothing left
+                // to process, and falling through would clobber `self.len` we just set.
+                debug_assert!(slice.len() == bytes_stolen);
+                return;
             }
-            slice = &slice[bytes_written..];
+            slice = &slice[bytes_stolen..];
         }
         let mut chunks = slice.chunks_exact(4);
-        while let Some(chunk) = chunks.next() {
+        for chunk in chunks.by_ref() {
             let value = u32::from_le_bytes(chunk.try_into().unwrap());
             marvin32_mix(&mut self.state, value);
         }
-        // Handle any leftover bytes
-        let bytes_written = self.buffer.write(chunks.remainder());
-        if cfg!(debug_assertions) {
-            let bytes_written = bytes_written.unwrap();
-            debug_assert_eq!(bytes_written, chunks.remainder().len());
-            debug_assert!(bytes_written < 4);
-        }
+        // Stash any leftover bytes for the next write() or finish()
+        let remainder = chunks.remainder();
+        debug_assert!(remainder.len() < 4);
+        self.buf[..remainder.len()].copy_from_slice(remainder);
+        self.len = remainder.len() as u8;
     }
 
     fn finish(&self) -> u64 {
-        let final_value = self.buffer.get_ref()[..self.buffer.position() as usize]
+        let final_value = self.buf[..self.len as usize]
             .iter()
             .rev()
             .fold(0x80, |state, byte| (state << 8) | *byte as u32);
@@ -188,18 +296,162 @@ impl Hasher for Marvin32 {
     }
 }
 
+#[cfg(feature = "digest")]
+/// Interop with the RustCrypto [`digest`] ecosystem (e.g. for use as the hash behind a
+/// `digest`-generic HMAC or streaming consumer).
+///
+/// The finalized output is the 4-byte **little-endian** encoding of the same `u32` returned by
+/// [`hash()`]/[`Hasher::finish()`].
+mod digest_impl {
+    use super::Marvin32;
+    use core::hash::Hasher;
+    use digest::consts::{U4, U8};
+    use digest::crypto_common::{KeyInit, KeySizeUser};
+    use digest::generic_array::GenericArray;
+    use digest::{FixedOutput, HashMarker, OutputSizeUser, Reset, Update};
+
+    impl HashMarker for Marvin32 {}
+
+    impl Update for Marvin32 {
+        fn update(&mut self, data: &[u8]) {
+            Hasher::write(self, data);
+        }
+    }
+
+    impl OutputSizeUser for Marvin32 {
+        type OutputSize = U4;
+    }
+
+    impl FixedOutput for Marvin32 {
+        fn finalize_into(self, out: &mut GenericArray<u8, U4>) {
+            let hash = Hasher::finish(&self) as u32;
+            out.copy_from_slice(&hash.to_le_bytes());
+        }
+    }
+
+    impl Reset for Marvin32 {
+        fn reset(&mut self) {
+            *self = Marvin32::new(self.seed);
+        }
+    }
+
+    impl KeySizeUser for Marvin32 {
+        type KeySize = U8;
+    }
+
+    impl KeyInit for Marvin32 {
+        fn new(key: &GenericArray<u8, U8>) -> Self {
+            Marvin32::new(u64::from_le_bytes((*key).into()))
+        }
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::Zeroize for Marvin32 {
+    fn zeroize(&mut self) {
+        self.seed.zeroize();
+        self.state.lo.zeroize();
+        self.state.hi.zeroize();
+        self.buf.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for Marvin32 {
+    fn drop(&mut self) {
+        zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl zeroize::ZeroizeOnDrop for Marvin32 {}
+
+#[cfg(feature = "serde")]
+/// On-the-wire representation of [`Marvin32`]'s incremental state: the original `seed`, the
+/// `lo`/`hi` mix words, and the 0-3 buffered tail bytes plus how many of them are filled, so a
+/// partially-consumed `write()` buffer round-trips exactly.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Marvin32Repr {
+    seed: u64,
+    lo: u32,
+    hi: u32,
+    buf: [u8; 4],
+    len: u8,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Marvin32 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Marvin32Repr {
+            seed: self.seed,
+            lo: self.state.lo,
+            hi: self.state.hi,
+            buf: self.buf,
+            len: self.len,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Marvin32 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let repr = Marvin32Repr::deserialize(deserializer)?;
+        if repr.len > 3 {
+            return Err(D::Error::custom("Marvin32 buffer length out of range"));
+        }
+        Ok(Marvin32 {
+            seed: repr.seed,
+            state: Marvin32State {
+                lo: repr.lo,
+                hi: repr.hi,
+            },
+            buf: repr.buf,
+            len: repr.len,
+        })
+    }
+}
+
 #[test]
 fn unit_test() {
-    const TEST: &'static [u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
+    const TEST: &[u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
     assert_eq!(TEST.len(), 14);
     let hash = marvin32_hash(TEST, 0x5D70D359C498B3F8);
     assert_eq!(hash, 0xba627c81, "mismatch in hash");
 }
 
+const _: () = assert!(hash_const(b"abcdefg", 0x5D70D359C498B3F8) == 0x79b01bfb);
+
+#[test]
+fn unit_test_hash_const_matches_hash() {
+    const LENGTHS: &[usize] = &[0, 1, 2, 3, 4, 5, 7, 8, 9, 15, 16, 17, 31, 32, 33];
+    const SEED: u64 = 0x5D70D359C498B3F8;
+    const BUF: [u8; 33] = {
+        let mut bytes = [0u8; 33];
+        let mut i = 0;
+        while i < bytes.len() {
+            bytes[i] = i as u8;
+            i += 1;
+        }
+        bytes
+    };
+
+    for &len in LENGTHS {
+        let slice = &BUF[..len];
+        assert_eq!(
+            hash_const(slice, SEED),
+            hash(slice, SEED),
+            "hash_const and hash disagree for a {len}-byte input"
+        );
+    }
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn unit_test_streaming() -> std::io::Result<()> {
-    const TEST: &'static [u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
+    const TEST: &[u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
     let mut cursor = std::io::Cursor::new(TEST);
     let hash = marvin32_hash_streaming(&mut cursor, 0x5D70D359C498B3F8)?;
     assert_eq!(hash, 0xba627c81, "mismatch in hash");
@@ -207,11 +459,94 @@ fn unit_test_streaming() -> std::io::Result<()> {
 }
 
 #[test]
-#[cfg(feature = "std")]
-fn unit_test_hasher() -> std::io::Result<()> {
-    const TEST: &'static [u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
+fn unit_test_hasher() {
+    const TEST: &[u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
     let mut hash = Marvin32::new(0x5D70D359C498B3F8);
     hash.write(TEST);
     assert_eq!(hash.finish(), 0xba627c81, "mismatch in hash");
-    Ok(())
+}
+
+#[test]
+fn unit_test_hasher_chunked_writes_match_one_shot() {
+    const SEED: u64 = 0x5D70D359C498B3F8;
+    const TEST: &[u8] = b"abc";
+
+    let mut one_shot = Marvin32::new(SEED);
+    one_shot.write(TEST);
+
+    // Feed the same bytes one at a time, none of which land on a 4-byte boundary, to make sure
+    // the result doesn't depend on how write() calls are chunked.
+    let mut chunked = Marvin32::new(SEED);
+    for &byte in TEST {
+        chunked.write(&[byte]);
+    }
+
+    assert_eq!(
+        chunked.finish(),
+        one_shot.finish(),
+        "chunked writes must produce the same hash as a single write()"
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn unit_test_build_hasher() {
+    use std::collections::HashMap;
+    use std::hash::BuildHasher;
+
+    let build_hasher = Marvin32BuildHasher::new(0x5D70D359C498B3F8);
+    assert_eq!(
+        build_hasher.build_hasher().finish(),
+        build_hasher.build_hasher().finish(),
+        "same fixed seed must produce the same empty-input hash"
+    );
+
+    let mut map: HashMap<&str, u32, RandomMarvin32> = HashMap::with_hasher(RandomMarvin32::new());
+    map.insert("hello", 1);
+    assert_eq!(map.get("hello"), Some(&1));
+}
+
+#[test]
+#[cfg(feature = "digest")]
+fn unit_test_digest() {
+    use digest::crypto_common::KeyInit;
+    use digest::{FixedOutput, Update};
+
+    const TEST: &[u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
+
+    let mut hasher =
+        <Marvin32 as KeyInit>::new(&0x5D70D359C498B3F8u64.to_le_bytes().into());
+    hasher.update(TEST);
+    let out = hasher.finalize_fixed();
+    assert_eq!(u32::from_le_bytes(out.into()), 0xba627c81, "mismatch in hash");
+}
+
+#[test]
+#[cfg(feature = "zeroize")]
+fn unit_test_zeroize() {
+    use zeroize::Zeroize;
+
+    let mut hasher = Marvin32::new(0x5D70D359C498B3F8);
+    hasher.write(b"abc");
+    hasher.zeroize();
+    assert_eq!(hasher.state.lo, 0);
+    assert_eq!(hasher.state.hi, 0);
+    assert_eq!(hasher.seed, 0);
+    assert_eq!(hasher.buf, [0u8; 4]);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn unit_test_serde_roundtrip() {
+    const TEST: &[u8] = b"A\0b\0c\0d\0e\0f\0g\0"; // "Abcdefg" in UTF-16-LE
+
+    let mut hasher = Marvin32::new(0x5D70D359C498B3F8);
+    // Split the write so we pause with a half-filled 4-byte buffer (2 of 4 bytes).
+    hasher.write(&TEST[..TEST.len() - 4]);
+
+    let serialized = serde_json::to_vec(&hasher).unwrap();
+    let mut resumed: Marvin32 = serde_json::from_slice(&serialized).unwrap();
+    resumed.write(&TEST[TEST.len() - 4..]);
+
+    assert_eq!(resumed.finish(), 0xba627c81, "mismatch in hash after resuming");
 }